@@ -0,0 +1,36 @@
+use super::super::pyobject::{PyContext, PyFuncArgs, PyObjectKind, PyObjectRef, PyResult};
+use super::super::vm::VirtualMachine;
+use super::objcodecs;
+
+pub fn init(context: &PyContext) {
+    let ref bytes_type = context.bytes_type;
+    bytes_type.set_attr("decode", context.new_rustfunc(bytes_decode));
+}
+
+pub fn get_value(obj: &PyObjectRef) -> Vec<u8> {
+    if let PyObjectKind::Bytes { value } = &obj.borrow().kind {
+        value.clone()
+    } else {
+        panic!("Inner error getting bytes");
+    }
+}
+
+fn bytes_decode(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(b, Some(vm.ctx.bytes_type()))],
+        optional = [(encoding, None), (errors, None)]
+    );
+    let value = get_value(&b);
+    let encoding = match encoding {
+        Some(e) => super::objstr::get_value(e),
+        None => "utf-8".to_string(),
+    };
+    let errors = match errors {
+        Some(e) => super::objstr::get_value(e),
+        None => "strict".to_string(),
+    };
+    let decoded = objcodecs::decode(vm, &value, &encoding, &errors)?;
+    Ok(vm.new_str(decoded))
+}
@@ -0,0 +1,175 @@
+// A small, centralized codec table shared by str.encode/bytes.decode and the
+// three-argument str(object, encoding, errors) constructor, so future
+// encodings only need to register here.
+
+use super::super::pyobject::PyObjectRef;
+use super::super::vm::VirtualMachine;
+
+enum Codec {
+    Utf8,
+    Ascii,
+    Latin1,
+    Utf16,
+}
+
+fn lookup(vm: &mut VirtualMachine, encoding: &str) -> Result<Codec, PyObjectRef> {
+    match encoding.to_lowercase().replace('_', "-").as_str() {
+        "utf-8" | "utf8" | "u8" => Ok(Codec::Utf8),
+        "ascii" | "us-ascii" | "646" => Ok(Codec::Ascii),
+        "latin-1" | "latin1" | "iso-8859-1" | "8859" | "cp819" | "l1" => Ok(Codec::Latin1),
+        "utf-16" | "utf16" => Ok(Codec::Utf16),
+        _ => Err(vm.new_value_error(format!("unknown encoding: {}", encoding))),
+    }
+}
+
+pub fn encode(
+    vm: &mut VirtualMachine,
+    value: &str,
+    encoding: &str,
+    errors: &str,
+) -> Result<Vec<u8>, PyObjectRef> {
+    let codec = lookup(vm, encoding)?;
+    match codec {
+        Codec::Utf8 => Ok(value.as_bytes().to_vec()),
+        Codec::Ascii => {
+            let mut out = Vec::with_capacity(value.len());
+            for c in value.chars() {
+                if c.is_ascii() {
+                    out.push(c as u8);
+                } else {
+                    match errors {
+                        "ignore" => {}
+                        "replace" => out.push(b'?'),
+                        _ => {
+                            return Err(vm.new_value_error(format!(
+                                "'ascii' codec can't encode character {:?}",
+                                c
+                            )));
+                        }
+                    }
+                }
+            }
+            Ok(out)
+        }
+        Codec::Latin1 => {
+            let mut out = Vec::with_capacity(value.len());
+            for c in value.chars() {
+                let code_point = c as u32;
+                if code_point <= 0xff {
+                    out.push(code_point as u8);
+                } else {
+                    match errors {
+                        "ignore" => {}
+                        "replace" => out.push(b'?'),
+                        _ => {
+                            return Err(vm.new_value_error(format!(
+                                "'latin-1' codec can't encode character {:?}",
+                                c
+                            )));
+                        }
+                    }
+                }
+            }
+            Ok(out)
+        }
+        Codec::Utf16 => {
+            let mut out = Vec::with_capacity(value.len() * 2);
+            for unit in value.encode_utf16() {
+                out.extend_from_slice(&unit.to_le_bytes());
+            }
+            Ok(out)
+        }
+    }
+}
+
+pub fn decode(
+    vm: &mut VirtualMachine,
+    data: &[u8],
+    encoding: &str,
+    errors: &str,
+) -> Result<String, PyObjectRef> {
+    let codec = lookup(vm, encoding)?;
+    match codec {
+        Codec::Utf8 => match std::str::from_utf8(data) {
+            Ok(s) => Ok(s.to_string()),
+            Err(_) if errors == "ignore" || errors == "replace" => {
+                let mut out = String::new();
+                let mut rest = data;
+                loop {
+                    match std::str::from_utf8(rest) {
+                        Ok(s) => {
+                            out.push_str(s);
+                            break;
+                        }
+                        Err(e) => {
+                            let valid_up_to = e.valid_up_to();
+                            out.push_str(std::str::from_utf8(&rest[..valid_up_to]).unwrap());
+                            let invalid_len = e.error_len().unwrap_or(rest.len() - valid_up_to);
+                            if errors == "replace" {
+                                out.push('\u{fffd}');
+                            }
+                            rest = &rest[valid_up_to + invalid_len..];
+                        }
+                    }
+                }
+                Ok(out)
+            }
+            Err(_) => Err(vm.new_value_error("invalid utf-8 sequence".to_string())),
+        },
+        Codec::Ascii => {
+            let mut out = String::with_capacity(data.len());
+            for &byte in data {
+                if byte < 0x80 {
+                    out.push(byte as char);
+                } else {
+                    match errors {
+                        "ignore" => {}
+                        "replace" => out.push('\u{fffd}'),
+                        _ => {
+                            return Err(vm.new_value_error(format!(
+                                "'ascii' codec can't decode byte 0x{:02x}",
+                                byte
+                            )));
+                        }
+                    }
+                }
+            }
+            Ok(out)
+        }
+        Codec::Latin1 => Ok(data.iter().map(|&byte| byte as char).collect()),
+        Codec::Utf16 => {
+            // Pairs high/low surrogates into a single code point and flags a
+            // lone surrogate as an error, same as the ruffle wide-string
+            // DecodeUtf16/CharIndices logic this mirrors.
+            let full_len = data.len() - (data.len() % 2);
+            let units = data[..full_len]
+                .chunks(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+            let mut out = String::new();
+            for unit in std::char::decode_utf16(units) {
+                match unit {
+                    Ok(c) => out.push(c),
+                    Err(_) => match errors {
+                        "ignore" => {}
+                        "replace" => out.push('\u{fffd}'),
+                        _ => {
+                            return Err(
+                                vm.new_value_error("unpaired surrogate in utf-16 data".to_string())
+                            );
+                        }
+                    },
+                }
+            }
+            // A trailing byte with no partner unit is handled under the same
+            // errors mode rather than always failing.
+            if data.len() % 2 != 0 {
+                match errors {
+                    "ignore" => {}
+                    "replace" => out.push('\u{fffd}'),
+                    _ => return Err(vm.new_value_error("truncated utf-16 data".to_string())),
+                }
+            }
+            Ok(out)
+        }
+    }
+}
@@ -2,8 +2,13 @@ use super::super::pyobject::{
     AttributeProtocol, PyContext, PyFuncArgs, PyObjectKind, PyObjectRef, PyResult, TypeProtocol,
 };
 use super::super::vm::VirtualMachine;
+use super::objbool;
+use super::objbytes;
+use super::objcodecs;
+use super::objdict;
 use super::objint;
 use super::objsequence::PySliceableSequence;
+use super::objtuple;
 use super::objtype;
 use num_bigint::ToBigInt;
 use num_traits::ToPrimitive;
@@ -19,11 +24,16 @@ pub fn init(context: &PyContext) {
     let ref str_type = context.str_type;
     str_type.set_attr("__add__", context.new_rustfunc(str_add));
     str_type.set_attr("__eq__", context.new_rustfunc(str_eq));
+    str_type.set_attr("__ne__", context.new_rustfunc(str_ne));
+    str_type.set_attr("__lt__", context.new_rustfunc(str_lt));
+    str_type.set_attr("__le__", context.new_rustfunc(str_le));
+    str_type.set_attr("__ge__", context.new_rustfunc(str_ge));
     str_type.set_attr("__contains__", context.new_rustfunc(str_contains));
     str_type.set_attr("__getitem__", context.new_rustfunc(str_getitem));
     str_type.set_attr("__gt__", context.new_rustfunc(str_gt));
     str_type.set_attr("__hash__", context.new_rustfunc(str_hash));
     str_type.set_attr("__len__", context.new_rustfunc(str_len));
+    str_type.set_attr("__mod__", context.new_rustfunc(str_mod));
     str_type.set_attr("__mul__", context.new_rustfunc(str_mul));
     str_type.set_attr("__new__", context.new_rustfunc(str_new));
     str_type.set_attr("__str__", context.new_rustfunc(str_str));
@@ -32,6 +42,10 @@ pub fn init(context: &PyContext) {
     str_type.set_attr("upper", context.new_rustfunc(str_upper));
     str_type.set_attr("capitalize", context.new_rustfunc(str_capitalize));
     str_type.set_attr("split", context.new_rustfunc(str_split));
+    str_type.set_attr("rsplit", context.new_rustfunc(str_rsplit));
+    str_type.set_attr("splitlines", context.new_rustfunc(str_splitlines));
+    str_type.set_attr("partition", context.new_rustfunc(str_partition));
+    str_type.set_attr("rpartition", context.new_rustfunc(str_rpartition));
     str_type.set_attr("strip", context.new_rustfunc(str_strip));
     str_type.set_attr("lstrip", context.new_rustfunc(str_lstrip));
     str_type.set_attr("rstrip", context.new_rustfunc(str_rstrip));
@@ -42,8 +56,14 @@ pub fn init(context: &PyContext) {
     str_type.set_attr("isalnum", context.new_rustfunc(str_isalnum));
     str_type.set_attr("isalpha", context.new_rustfunc(str_isalpha));
     str_type.set_attr("isdigit", context.new_rustfunc(str_isdigit));
+    str_type.set_attr("casefold", context.new_rustfunc(str_casefold));
+    str_type.set_attr("encode", context.new_rustfunc(str_encode));
 
-    // str_type.set_attr("center", context.new_rustfunc(str_center));
+    str_type.set_attr("center", context.new_rustfunc(str_center));
+    str_type.set_attr("ljust", context.new_rustfunc(str_ljust));
+    str_type.set_attr("rjust", context.new_rustfunc(str_rjust));
+    str_type.set_attr("zfill", context.new_rustfunc(str_zfill));
+    str_type.set_attr("expandtabs", context.new_rustfunc(str_expandtabs));
 }
 
 pub fn get_value(obj: &PyObjectRef) -> String {
@@ -84,6 +104,62 @@ fn str_gt(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     Ok(vm.ctx.new_bool(result))
 }
 
+fn str_lt(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(zelf, Some(vm.ctx.str_type())), (other, None)]
+    );
+    let result = if objtype::isinstance(other, &vm.ctx.str_type()) {
+        get_value(zelf) < get_value(other)
+    } else {
+        false
+    };
+    Ok(vm.ctx.new_bool(result))
+}
+
+fn str_le(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(zelf, Some(vm.ctx.str_type())), (other, None)]
+    );
+    let result = if objtype::isinstance(other, &vm.ctx.str_type()) {
+        get_value(zelf) <= get_value(other)
+    } else {
+        false
+    };
+    Ok(vm.ctx.new_bool(result))
+}
+
+fn str_ge(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(zelf, Some(vm.ctx.str_type())), (other, None)]
+    );
+    let result = if objtype::isinstance(other, &vm.ctx.str_type()) {
+        get_value(zelf) >= get_value(other)
+    } else {
+        false
+    };
+    Ok(vm.ctx.new_bool(result))
+}
+
+fn str_ne(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(zelf, Some(vm.ctx.str_type())), (other, None)]
+    );
+    let result = if objtype::isinstance(other, &vm.ctx.str_type()) {
+        get_value(zelf) != get_value(other)
+    } else {
+        true
+    };
+    Ok(vm.ctx.new_bool(result))
+}
+
 fn str_str(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     arg_check!(vm, args, required = [(s, Some(vm.ctx.str_type()))]);
     Ok(s.clone())
@@ -151,7 +227,7 @@ fn str_hash(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
 fn str_len(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     arg_check!(vm, args, required = [(s, Some(vm.ctx.str_type()))]);
     let sv = get_value(s);
-    Ok(vm.ctx.new_int(sv.len().to_bigint().unwrap()))
+    Ok(vm.ctx.new_int(sv.chars().count().to_bigint().unwrap()))
 }
 
 fn str_mul(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
@@ -193,21 +269,248 @@ fn str_capitalize(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     Ok(vm.ctx.new_str(capitalized))
 }
 
+fn get_maxsplit(maxsplit: Option<&PyObjectRef>) -> i64 {
+    match maxsplit {
+        Some(n) => objint::get_value(n).to_i64().unwrap(),
+        None => -1,
+    }
+}
+
 fn str_split(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type()))],
+        optional = [(pat, None), (maxsplit, None)]
+    );
+    let value = get_value(&s);
+    let maxsplit = get_maxsplit(maxsplit);
+    let elements = match pat {
+        Some(pat) => {
+            let pat = get_value(pat);
+            if pat.is_empty() {
+                return Err(vm.new_value_error("empty separator".to_string()));
+            }
+            if maxsplit < 0 {
+                value.split(pat.as_str()).map(str::to_string).collect()
+            } else {
+                value
+                    .splitn(maxsplit as usize + 1, pat.as_str())
+                    .map(str::to_string)
+                    .collect()
+            }
+        }
+        None => {
+            if maxsplit < 0 {
+                value.split_whitespace().map(str::to_string).collect()
+            } else {
+                split_whitespace_maxsplit(&value, maxsplit as usize)
+            }
+        }
+    };
+    Ok(vm
+        .ctx
+        .new_list(elements.into_iter().map(|o| vm.ctx.new_str(o)).collect()))
+}
+
+fn split_whitespace_maxsplit(value: &str, maxsplit: usize) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut rest = value.trim_start();
+    // An empty (or all-whitespace) string has nothing to yield, matching
+    // CPython's `[]` rather than a bogus `['']`.
+    if rest.is_empty() {
+        return result;
+    }
+    while result.len() < maxsplit {
+        match rest.find(char::is_whitespace) {
+            Some(idx) => {
+                result.push(rest[..idx].to_string());
+                rest = rest[idx..].trim_start();
+            }
+            None => break,
+        }
+    }
+    // Only rstrip the remainder when it's genuinely the end of the string
+    // (the loop ran out of separators); once maxsplit is hit, whatever is
+    // left - including any trailing whitespace - becomes the final element.
+    if result.len() < maxsplit {
+        rest = rest.trim_end();
+    }
+    if !rest.is_empty() {
+        result.push(rest.to_string());
+    }
+    result
+}
+
+fn str_rsplit(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type()))],
+        optional = [(pat, None), (maxsplit, None)]
+    );
+    let value = get_value(&s);
+    let maxsplit = get_maxsplit(maxsplit);
+    let mut elements: Vec<String> = match pat {
+        Some(pat) => {
+            let pat = get_value(pat);
+            if pat.is_empty() {
+                return Err(vm.new_value_error("empty separator".to_string()));
+            }
+            if maxsplit < 0 {
+                value.rsplit(pat.as_str()).map(str::to_string).collect()
+            } else {
+                value
+                    .rsplitn(maxsplit as usize + 1, pat.as_str())
+                    .map(str::to_string)
+                    .collect()
+            }
+        }
+        None => {
+            if maxsplit < 0 {
+                value.split_whitespace().rev().map(str::to_string).collect()
+            } else {
+                let maxsplit = maxsplit as usize;
+                let mut result = Vec::new();
+                let mut rest = value.trim_end();
+                // An empty (or all-whitespace) string has nothing to yield,
+                // matching CPython's `[]` rather than a bogus `['']`.
+                if !rest.is_empty() {
+                    while result.len() < maxsplit {
+                        match rest.rfind(char::is_whitespace) {
+                            Some(idx) => {
+                                let ws_len = rest[idx..].chars().next().unwrap().len_utf8();
+                                result.push(rest[idx + ws_len..].to_string());
+                                rest = rest[..idx].trim_end();
+                            }
+                            None => break,
+                        }
+                    }
+                    // Only lstrip the remainder when it's genuinely the start
+                    // of the string (the loop ran out of separators); once
+                    // maxsplit is hit, whatever is left - including any
+                    // leading whitespace - becomes the final element.
+                    if result.len() < maxsplit {
+                        rest = rest.trim_start();
+                    }
+                    if !rest.is_empty() {
+                        result.push(rest.to_string());
+                    }
+                }
+                result
+            }
+        }
+    };
+    // rsplit/rsplitn already yield elements in right-to-left order; restore
+    // left-to-right order to match CPython's list result.
+    elements.reverse();
+    Ok(vm
+        .ctx
+        .new_list(elements.into_iter().map(|o| vm.ctx.new_str(o)).collect()))
+}
+
+fn str_splitlines(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type()))],
+        optional = [(keepends, None)]
+    );
+    let value = get_value(&s);
+    let keepends = match keepends {
+        Some(k) => objbool::boolval(vm, k.clone())?,
+        None => false,
+    };
+
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut chars = value.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\n' | '\u{0b}' | '\u{0c}' | '\u{1c}' | '\u{1d}' | '\u{1e}' | '\u{85}'
+            | '\u{2028}' | '\u{2029}' => {
+                let end = idx + c.len_utf8();
+                lines.push(if keepends {
+                    value[line_start..end].to_string()
+                } else {
+                    value[line_start..idx].to_string()
+                });
+                line_start = end;
+            }
+            '\r' => {
+                let mut end = idx + c.len_utf8();
+                if let Some(&(_, '\n')) = chars.peek() {
+                    chars.next();
+                    end += 1;
+                }
+                lines.push(if keepends {
+                    value[line_start..end].to_string()
+                } else {
+                    value[line_start..idx].to_string()
+                });
+                line_start = end;
+            }
+            _ => {}
+        }
+    }
+    if line_start < value.len() {
+        lines.push(value[line_start..].to_string());
+    }
+    Ok(vm
+        .ctx
+        .new_list(lines.into_iter().map(|o| vm.ctx.new_str(o)).collect()))
+}
+
+fn str_partition(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type())), (pat, Some(vm.ctx.str_type()))]
+    );
+    let value = get_value(&s);
+    let pat = get_value(&pat);
+    if pat.is_empty() {
+        return Err(vm.new_value_error("empty separator".to_string()));
+    }
+    let parts = match value.find(pat.as_str()) {
+        Some(idx) => (
+            value[..idx].to_string(),
+            pat.clone(),
+            value[idx + pat.len()..].to_string(),
+        ),
+        None => (value.clone(), String::new(), String::new()),
+    };
+    Ok(vm.ctx.new_tuple(vec![
+        vm.ctx.new_str(parts.0),
+        vm.ctx.new_str(parts.1),
+        vm.ctx.new_str(parts.2),
+    ]))
+}
+
+fn str_rpartition(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     arg_check!(
         vm,
         args,
         required = [(s, Some(vm.ctx.str_type())), (pat, Some(vm.ctx.str_type()))]
     );
     let value = get_value(&s);
-    // if some
     let pat = get_value(&pat);
-    let str_pat = pat.as_str();
-    let elements = value
-        .split(str_pat)
-        .map(|o| vm.ctx.new_str(o.to_string()))
-        .collect();
-    Ok(vm.ctx.new_list(elements))
+    if pat.is_empty() {
+        return Err(vm.new_value_error("empty separator".to_string()));
+    }
+    let parts = match value.rfind(pat.as_str()) {
+        Some(idx) => (
+            value[..idx].to_string(),
+            pat.clone(),
+            value[idx + pat.len()..].to_string(),
+        ),
+        None => (String::new(), String::new(), value.clone()),
+    };
+    Ok(vm.ctx.new_tuple(vec![
+        vm.ctx.new_str(parts.0),
+        vm.ctx.new_str(parts.1),
+        vm.ctx.new_str(parts.2),
+    ]))
 }
 
 fn str_strip(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
@@ -228,15 +531,52 @@ fn str_rstrip(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     Ok(vm.ctx.new_str(value))
 }
 
+// An explicit Case::Sens/Case::Insens split for text comparisons, rather than
+// threading a bare bool through every call site.
+enum Case {
+    Sens,
+    Insens,
+}
+
+fn casefold(value: &str) -> String {
+    // Rust's char::to_lowercase is not full Unicode case folding, but it is
+    // the same approximation the rest of this module already uses (see
+    // str_lower/str_title), so caseless comparisons stay consistent.
+    value.chars().flat_map(char::to_lowercase).collect()
+}
+
+fn get_case(case_insensitive: Option<&PyObjectRef>) -> Case {
+    match case_insensitive {
+        Some(flag) => {
+            if let PyObjectKind::Boolean { value: true } = flag.borrow().kind {
+                Case::Insens
+            } else {
+                Case::Sens
+            }
+        }
+        None => Case::Sens,
+    }
+}
+
 fn str_endswith(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     arg_check!(
         vm,
         args,
-        required = [(s, Some(vm.ctx.str_type())), (pat, Some(vm.ctx.str_type()))]
+        required = [(s, Some(vm.ctx.str_type())), (pat, Some(vm.ctx.str_type()))],
+        optional = [(case_insensitive, None)]
     );
     let value = get_value(&s);
     let pat = get_value(&pat);
-    Ok(vm.ctx.new_bool(value.ends_with(pat.as_str())))
+    let result = match get_case(case_insensitive) {
+        Case::Sens => value.ends_with(pat.as_str()),
+        Case::Insens => casefold(&value).ends_with(casefold(&pat).as_str()),
+    };
+    Ok(vm.ctx.new_bool(result))
+}
+
+fn str_casefold(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(vm, args, required = [(s, Some(vm.ctx.str_type()))]);
+    Ok(vm.ctx.new_str(casefold(&get_value(&s))))
 }
 
 fn str_swapcase(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
@@ -275,28 +615,629 @@ fn str_title(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     Ok(vm.ctx.new_str(titled_str))
 }
 
-// fn str_center(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
-//     arg_check!(
-//         vm,
-//         args,
-//         required = [(s, Some(vm.ctx.str_type())), (len, Some(vm.ctx.int_type()))],
-//         optional = [(chars, None)]
-//     );
-//     let value = get_value(&s);
-//     let len = get_value(&len).parse::<usize>();
-//     let chars = args.get_kwargs
-//     Ok(vm.ctx.new_str(value))
-// }
+// Looks up the values to be substituted into a `%`-format string: either a
+// single value, a tuple consumed positionally, or a dict consulted by the
+// `(mapping_key)` syntax.
+enum ModArgs {
+    Dict(PyObjectRef),
+    Positional(Vec<PyObjectRef>, usize),
+}
+
+impl ModArgs {
+    fn get_keyed(&self, vm: &mut VirtualMachine, key: &str) -> PyResult {
+        match self {
+            ModArgs::Dict(d) => objdict::get_item(vm, d, key),
+            ModArgs::Positional(_, _) => {
+                Err(vm.new_type_error("format requires a mapping".to_string()))
+            }
+        }
+    }
+
+    fn get_next(&mut self, vm: &mut VirtualMachine) -> PyResult {
+        match self {
+            ModArgs::Dict(_) => {
+                Err(vm.new_type_error("not enough arguments for format string".to_string()))
+            }
+            ModArgs::Positional(values, pos) => {
+                if *pos >= values.len() {
+                    return Err(
+                        vm.new_type_error("not enough arguments for format string".to_string())
+                    );
+                }
+                let value = values[*pos].clone();
+                *pos += 1;
+                Ok(value)
+            }
+        }
+    }
+}
+
+fn format_uses_mapping(format_str: &str) -> bool {
+    let mut chars = format_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            continue;
+        }
+        if chars.peek() == Some(&'(') {
+            return true;
+        }
+    }
+    false
+}
+
+fn mod_arg_as_i64(vm: &mut VirtualMachine, obj: &PyObjectRef) -> Result<i64, PyObjectRef> {
+    if !objtype::isinstance(obj, &vm.ctx.int_type()) {
+        return Err(vm.new_type_error("* wants int".to_string()));
+    }
+    Ok(objint::get_value(obj).to_i64().unwrap())
+}
+
+fn str_mod(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type())), (values, None)]
+    );
+    let format_str = get_value(&s);
+
+    // CPython only treats the right-hand side as a mapping when the format
+    // string actually uses a `%(key)` specifier; otherwise a dict is just a
+    // single positional value like any other object.
+    let mut mod_args = if format_uses_mapping(&format_str) {
+        if objtype::isinstance(values, &vm.ctx.dict_type()) {
+            ModArgs::Dict(values.clone())
+        } else {
+            return Err(vm.new_type_error("format requires a mapping".to_string()));
+        }
+    } else if objtype::isinstance(values, &vm.ctx.tuple_type()) {
+        ModArgs::Positional(objtuple::get_elements(values), 0)
+    } else {
+        ModArgs::Positional(vec![values.clone()], 0)
+    };
+
+    let mut chars = format_str.chars().peekable();
+    let mut result = String::new();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+
+        // %% is a literal percent sign and consumes no arguments.
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            result.push('%');
+            continue;
+        }
+
+        // optional (mapping_key)
+        let mut mapping_key = None;
+        if chars.peek() == Some(&'(') {
+            chars.next();
+            let mut key = String::new();
+            loop {
+                match chars.next() {
+                    Some(')') => break,
+                    Some(k) => key.push(k),
+                    None => {
+                        return Err(vm.new_value_error("incomplete format key".to_string()));
+                    }
+                }
+            }
+            mapping_key = Some(key);
+        }
+
+        // flags: - 0 + space #
+        let mut left_justify = false;
+        let mut zero_pad = false;
+        let mut force_sign = false;
+        let mut blank_sign = false;
+        let mut alt_form = false;
+        while let Some(&flag) = chars.peek() {
+            match flag {
+                '-' => left_justify = true,
+                '0' => zero_pad = true,
+                '+' => force_sign = true,
+                ' ' => blank_sign = true,
+                '#' => alt_form = true,
+                _ => break,
+            }
+            chars.next();
+        }
+
+        // width, possibly '*'
+        let mut width = None;
+        if chars.peek() == Some(&'*') {
+            chars.next();
+            let arg = if let Some(key) = &mapping_key {
+                mod_args.get_keyed(vm, key)?
+            } else {
+                mod_args.get_next(vm)?
+            };
+            let w = mod_arg_as_i64(vm, &arg)?;
+            // A negative '*' width means left-justify in a field of the
+            // absolute width, matching CPython.
+            if w < 0 {
+                left_justify = true;
+                width = Some((-w) as usize);
+            } else {
+                width = Some(w as usize);
+            }
+        } else {
+            let mut digits = String::new();
+            while let Some(&d) = chars.peek() {
+                if d.is_ascii_digit() {
+                    digits.push(d);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            if !digits.is_empty() {
+                width = Some(digits.parse().unwrap());
+            }
+        }
+
+        // .precision, possibly '*'
+        let mut precision = None;
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            if chars.peek() == Some(&'*') {
+                chars.next();
+                let arg = if let Some(key) = &mapping_key {
+                    mod_args.get_keyed(vm, key)?
+                } else {
+                    mod_args.get_next(vm)?
+                };
+                let p = mod_arg_as_i64(vm, &arg)?;
+                // A negative '*' precision is treated as if omitted.
+                precision = if p < 0 { None } else { Some(p as usize) };
+            } else {
+                let mut digits = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_digit() {
+                        digits.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                precision = Some(digits.parse().unwrap_or(0));
+            }
+        }
+
+        let conversion = match chars.next() {
+            Some(c) => c,
+            None => return Err(vm.new_value_error("incomplete format".to_string())),
+        };
+
+        let arg = if let Some(key) = &mapping_key {
+            mod_args.get_keyed(vm, key)?
+        } else {
+            mod_args.get_next(vm)?
+        };
+
+        let mut piece = match conversion {
+            's' => get_value(&vm.to_str(&arg)?),
+            'r' => get_value(&vm.to_repr(&arg)?),
+            'd' | 'i' => format_int(vm, &arg, 10, false, force_sign, blank_sign, precision)?,
+            'o' => {
+                format_int_prefixed(vm, &arg, 8, alt_form, "0o", force_sign, blank_sign, precision)?
+            }
+            'x' => {
+                format_int_prefixed(vm, &arg, 16, alt_form, "0x", force_sign, blank_sign, precision)?
+            }
+            'X' => {
+                format_int_prefixed(
+                    vm, &arg, 16, alt_form, "0X", force_sign, blank_sign, precision,
+                )?
+                .to_uppercase()
+            }
+            'e' => format_float(vm, &arg, precision.unwrap_or(6), force_sign, false, false)?,
+            'E' => format_float(vm, &arg, precision.unwrap_or(6), force_sign, false, true)?,
+            'f' | 'F' => format_float(vm, &arg, precision.unwrap_or(6), force_sign, true, false)?,
+            'g' => format_general(vm, &arg, precision.unwrap_or(6), force_sign, false)?,
+            'G' => format_general(vm, &arg, precision.unwrap_or(6), force_sign, true)?,
+            'c' => format_char(vm, &arg)?,
+            _ => {
+                return Err(vm.new_value_error(format!(
+                    "unsupported format character '{}'",
+                    conversion
+                )));
+            }
+        };
+
+        if let Some(prec) = precision {
+            if conversion == 's' || conversion == 'r' {
+                piece = piece.chars().take(prec).collect();
+            }
+        }
+
+        // A precision on an integer conversion makes the '0' flag a no-op,
+        // same as C's printf.
+        let zero_pad = zero_pad && !(precision.is_some() && "dioxX".contains(conversion));
+
+        if let Some(w) = width {
+            let pad = w.saturating_sub(piece.chars().count());
+            if pad > 0 {
+                if left_justify {
+                    piece.push_str(&" ".repeat(pad));
+                } else if zero_pad && "dioxXeEfFgG".contains(conversion) {
+                    let prefix_len = numeric_prefix_len(&piece);
+                    let (prefix, rest) = piece.split_at(prefix_len);
+                    piece = format!("{}{}{}", prefix, "0".repeat(pad), rest);
+                } else {
+                    let mut padded = " ".repeat(pad);
+                    padded.push_str(&piece);
+                    piece = padded;
+                }
+            }
+        }
+
+        result.push_str(&piece);
+    }
+
+    if let ModArgs::Positional(values, pos) = &mod_args {
+        if *pos != values.len() {
+            return Err(vm.new_type_error(
+                "not all arguments converted during string formatting".to_string(),
+            ));
+        }
+    }
+
+    Ok(vm.ctx.new_str(result))
+}
+
+fn split_sign(s: &str) -> (&str, &str) {
+    if s.starts_with('+') || s.starts_with('-') {
+        s.split_at(1)
+    } else {
+        ("", s)
+    }
+}
+
+fn format_int(
+    vm: &mut VirtualMachine,
+    arg: &PyObjectRef,
+    radix: u32,
+    _alt_form: bool,
+    force_sign: bool,
+    blank_sign: bool,
+    precision: Option<usize>,
+) -> Result<String, PyObjectRef> {
+    if !objtype::isinstance(arg, &vm.ctx.int_type()) {
+        return Err(vm.new_type_error("%d format: a number is required".to_string()));
+    }
+    let value = objint::get_value(arg);
+    let mut unsigned = value.magnitude().to_str_radix(radix);
+    // precision on an integer conversion is a minimum digit count, padded
+    // with zeros before the sign/prefix are applied
+    if let Some(prec) = precision {
+        if unsigned.len() < prec {
+            unsigned = format!("{}{}", "0".repeat(prec - unsigned.len()), unsigned);
+        }
+    }
+    let sign = if value.to_i64().unwrap_or(0) < 0 {
+        "-"
+    } else if force_sign {
+        "+"
+    } else if blank_sign {
+        " "
+    } else {
+        ""
+    };
+    Ok(format!("{}{}", sign, unsigned))
+}
+
+// The length of the sign + base-prefix portion of an already-formatted
+// integer piece (e.g. "-0x" in "-0xff"), so zero-padding for width can be
+// inserted after it instead of splitting it apart.
+fn numeric_prefix_len(s: &str) -> usize {
+    let mut len = if s.starts_with('+') || s.starts_with('-') || s.starts_with(' ') {
+        1
+    } else {
+        0
+    };
+    let rest = &s[len..];
+    if rest.len() >= 2 && rest.is_char_boundary(2) {
+        let two = &rest[..2];
+        if two.eq_ignore_ascii_case("0x") || two.eq_ignore_ascii_case("0o") {
+            len += 2;
+        }
+    }
+    len
+}
+
+fn format_int_prefixed(
+    vm: &mut VirtualMachine,
+    arg: &PyObjectRef,
+    radix: u32,
+    alt_form: bool,
+    prefix: &str,
+    force_sign: bool,
+    blank_sign: bool,
+    precision: Option<usize>,
+) -> Result<String, PyObjectRef> {
+    let digits = format_int(vm, arg, radix, false, force_sign, blank_sign, precision)?;
+    if alt_form {
+        let (sign, rest) = split_sign(&digits);
+        Ok(format!("{}{}{}", sign, prefix, rest))
+    } else {
+        Ok(digits)
+    }
+}
+
+fn mod_arg_as_f64(vm: &mut VirtualMachine, arg: &PyObjectRef) -> Result<f64, PyObjectRef> {
+    if objtype::isinstance(arg, &vm.ctx.float_type()) {
+        Ok(super::objfloat::get_value(arg))
+    } else if objtype::isinstance(arg, &vm.ctx.int_type()) {
+        Ok(objint::get_value(arg).to_f64().unwrap())
+    } else {
+        Err(vm.new_type_error("float argument required".to_string()))
+    }
+}
+
+// Rust's `{:e}` leaves the exponent unsigned and unpadded (e.g. "1.2e8"); C's
+// (and CPython's) %e/%g use a signed, zero-padded minimum-two-digit exponent
+// (e.g. "1.2e+08"). Reformat to match.
+fn cpython_exponent(rust_exp_notation: &str) -> String {
+    let e_pos = rust_exp_notation.find('e').unwrap();
+    let mantissa = &rust_exp_notation[..e_pos];
+    let exp: i32 = rust_exp_notation[e_pos + 1..].parse().unwrap();
+    let exp_sign = if exp < 0 { '-' } else { '+' };
+    format!("{}e{}{:02}", mantissa, exp_sign, exp.abs())
+}
+
+fn apply_sign(formatted: String, value: f64, force_sign: bool) -> String {
+    if force_sign && value >= 0.0 {
+        format!("+{}", formatted)
+    } else {
+        formatted
+    }
+}
+
+fn format_float(
+    vm: &mut VirtualMachine,
+    arg: &PyObjectRef,
+    precision: usize,
+    force_sign: bool,
+    fixed: bool,
+    upper: bool,
+) -> Result<String, PyObjectRef> {
+    let value = mod_arg_as_f64(vm, arg)?;
+    let mut formatted = if fixed {
+        format!("{:.*}", precision, value)
+    } else {
+        cpython_exponent(&format!("{:.*e}", precision, value))
+    };
+    if upper {
+        formatted = formatted.to_uppercase();
+    }
+    Ok(apply_sign(formatted, value, force_sign))
+}
+
+fn strip_trailing_zeros(s: &str) -> String {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+// Implements the C/CPython %g algorithm: pick fixed or scientific notation
+// based on the decimal exponent, format with `precision` significant digits,
+// then strip insignificant trailing zeros.
+fn format_general(
+    vm: &mut VirtualMachine,
+    arg: &PyObjectRef,
+    precision: usize,
+    force_sign: bool,
+    upper: bool,
+) -> Result<String, PyObjectRef> {
+    let value = mod_arg_as_f64(vm, arg)?;
+    let sig_digits = if precision == 0 { 1 } else { precision };
+
+    let sci = format!("{:.*e}", sig_digits - 1, value);
+    let e_pos = sci.find('e').unwrap();
+    let exponent: i32 = sci[e_pos + 1..].parse().unwrap();
+
+    let mut formatted = if exponent < -4 || exponent >= sig_digits as i32 {
+        let mantissa = strip_trailing_zeros(&sci[..e_pos]);
+        let exp_sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", mantissa, exp_sign, exponent.abs())
+    } else {
+        let frac_digits = (sig_digits as i32 - 1 - exponent).max(0) as usize;
+        strip_trailing_zeros(&format!("{:.*}", frac_digits, value))
+    };
+    if upper {
+        formatted = formatted.to_uppercase();
+    }
+    Ok(apply_sign(formatted, value, force_sign))
+}
+
+fn format_char(vm: &mut VirtualMachine, arg: &PyObjectRef) -> Result<String, PyObjectRef> {
+    if objtype::isinstance(arg, &vm.ctx.str_type()) {
+        let s = get_value(arg);
+        if s.chars().count() != 1 {
+            return Err(vm.new_type_error("%c requires int or char".to_string()));
+        }
+        Ok(s)
+    } else if objtype::isinstance(arg, &vm.ctx.int_type()) {
+        let code_point = objint::get_value(arg).to_u32().unwrap();
+        match std::char::from_u32(code_point) {
+            Some(c) => Ok(c.to_string()),
+            None => Err(vm.new_value_error("%c arg not in range(0x110000)".to_string())),
+        }
+    } else {
+        Err(vm.new_type_error("%c requires int or char".to_string()))
+    }
+}
+
+// CPython treats a negative (or otherwise unrepresentable) width as 0 rather
+// than erroring, so center/ljust/rjust/zfill leave the string unchanged.
+fn get_width(len: &PyObjectRef) -> usize {
+    objint::get_value(len).to_usize().unwrap_or(0)
+}
+
+fn get_fillchar(vm: &mut VirtualMachine, fillchar: Option<&PyObjectRef>) -> Result<char, PyObjectRef> {
+    match fillchar {
+        Some(c) => {
+            let s = get_value(c);
+            if s.chars().count() != 1 {
+                return Err(
+                    vm.new_type_error("The fill character must be exactly one character long".to_string())
+                );
+            }
+            Ok(s.chars().next().unwrap())
+        }
+        None => Ok(' '),
+    }
+}
+
+fn str_center(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type())), (len, Some(vm.ctx.int_type()))],
+        optional = [(fillchar, None)]
+    );
+    let value = get_value(&s);
+    let width = get_width(&len);
+    let fillchar = get_fillchar(vm, fillchar)?;
+
+    let orig_len = value.chars().count();
+    if orig_len >= width {
+        return Ok(vm.ctx.new_str(value));
+    }
+    let marg = width - orig_len;
+    // an odd amount of fill puts the extra character on the right when width
+    // is even and on the left when width is odd, matching CPython
+    let left = marg / 2 + (marg & width & 1);
+    let right = marg - left;
+    let mut result = String::with_capacity(width);
+    result.extend(std::iter::repeat(fillchar).take(left));
+    result.push_str(&value);
+    result.extend(std::iter::repeat(fillchar).take(right));
+    Ok(vm.ctx.new_str(result))
+}
+
+fn str_ljust(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type())), (len, Some(vm.ctx.int_type()))],
+        optional = [(fillchar, None)]
+    );
+    let value = get_value(&s);
+    let width = get_width(&len);
+    let fillchar = get_fillchar(vm, fillchar)?;
+
+    let orig_len = value.chars().count();
+    if orig_len >= width {
+        return Ok(vm.ctx.new_str(value));
+    }
+    let mut result = value;
+    result.extend(std::iter::repeat(fillchar).take(width - orig_len));
+    Ok(vm.ctx.new_str(result))
+}
+
+fn str_rjust(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type())), (len, Some(vm.ctx.int_type()))],
+        optional = [(fillchar, None)]
+    );
+    let value = get_value(&s);
+    let width = get_width(&len);
+    let fillchar = get_fillchar(vm, fillchar)?;
+
+    let orig_len = value.chars().count();
+    if orig_len >= width {
+        return Ok(vm.ctx.new_str(value));
+    }
+    let mut result: String = std::iter::repeat(fillchar).take(width - orig_len).collect();
+    result.push_str(&value);
+    Ok(vm.ctx.new_str(result))
+}
+
+fn str_zfill(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type())), (len, Some(vm.ctx.int_type()))]
+    );
+    let value = get_value(&s);
+    let width = get_width(&len);
+
+    let orig_len = value.chars().count();
+    if orig_len >= width {
+        return Ok(vm.ctx.new_str(value));
+    }
+    let (sign, rest) = split_sign(&value);
+    let mut result = String::with_capacity(width);
+    result.push_str(sign);
+    result.extend(std::iter::repeat('0').take(width - orig_len));
+    result.push_str(rest);
+    Ok(vm.ctx.new_str(result))
+}
+
+fn str_expandtabs(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type()))],
+        optional = [(tabsize, None)]
+    );
+    let value = get_value(&s);
+    let tabsize = match tabsize {
+        Some(t) => objint::get_value(t).to_usize().unwrap(),
+        None => 8,
+    };
+
+    let mut result = String::with_capacity(value.len());
+    let mut column = 0;
+    for c in value.chars() {
+        match c {
+            '\t' => {
+                if tabsize > 0 {
+                    let spaces = tabsize - (column % tabsize);
+                    result.extend(std::iter::repeat(' ').take(spaces));
+                    column += spaces;
+                }
+            }
+            '\n' | '\r' => {
+                result.push(c);
+                column = 0;
+            }
+            _ => {
+                result.push(c);
+                column += 1;
+            }
+        }
+    }
+    Ok(vm.ctx.new_str(result))
+}
 
 fn str_startswith(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     arg_check!(
         vm,
         args,
-        required = [(s, Some(vm.ctx.str_type())), (pat, Some(vm.ctx.str_type()))]
+        required = [(s, Some(vm.ctx.str_type())), (pat, Some(vm.ctx.str_type()))],
+        optional = [(case_insensitive, None)]
     );
     let value = get_value(&s);
     let pat = get_value(&pat);
-    Ok(vm.ctx.new_bool(value.starts_with(pat.as_str())))
+    let result = match get_case(case_insensitive) {
+        Case::Sens => value.starts_with(pat.as_str()),
+        Case::Insens => casefold(&value).starts_with(casefold(&pat).as_str()),
+    };
+    Ok(vm.ctx.new_bool(result))
 }
 
 fn str_contains(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
@@ -306,11 +1247,16 @@ fn str_contains(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
         required = [
             (s, Some(vm.ctx.str_type())),
             (needle, Some(vm.ctx.str_type()))
-        ]
+        ],
+        optional = [(case_insensitive, None)]
     );
     let value = get_value(&s);
     let needle = get_value(&needle);
-    Ok(vm.ctx.new_bool(value.contains(needle.as_str())))
+    let result = match get_case(case_insensitive) {
+        Case::Sens => value.contains(needle.as_str()),
+        Case::Insens => casefold(&value).contains(casefold(&needle).as_str()),
+    };
+    Ok(vm.ctx.new_bool(result))
 }
 
 fn str_isalnum(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
@@ -368,7 +1314,6 @@ fn str_getitem(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
     subscript(vm, &value, needle.clone())
 }
 
-// TODO: should with following format
 // class str(object='')
 // class str(object=b'', encoding='utf-8', errors='strict')
 fn str_new(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
@@ -376,22 +1321,72 @@ fn str_new(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
         return Ok(vm.new_str("".to_string()));
     }
 
-    if args.args.len() > 2 {
-        panic!("str expects exactly one parameter");
+    if args.args.len() == 2 {
+        return vm.to_str(&args.args[1]);
+    }
+
+    if args.args.len() > 4 {
+        return Err(vm.new_type_error("str expects at most three parameters".to_string()));
+    }
+
+    let object = &args.args[1];
+    if !objtype::isinstance(object, &vm.ctx.bytes_type()) {
+        return Err(vm.new_type_error(format!(
+            "decoding to str: need bytes, not {:?}",
+            object
+        )));
+    }
+    let encoding = if args.args.len() > 2 {
+        get_value(&args.args[2])
+    } else {
+        "utf-8".to_string()
+    };
+    let errors = if args.args.len() > 3 {
+        get_value(&args.args[3])
+    } else {
+        "strict".to_string()
     };
+    let data = objbytes::get_value(object);
+    let decoded = objcodecs::decode(vm, &data, &encoding, &errors)?;
+    Ok(vm.new_str(decoded))
+}
 
-    vm.to_str(&args.args[1])
+fn str_encode(vm: &mut VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(s, Some(vm.ctx.str_type()))],
+        optional = [(encoding, None), (errors, None)]
+    );
+    let value = get_value(&s);
+    let encoding = match encoding {
+        Some(e) => get_value(e),
+        None => "utf-8".to_string(),
+    };
+    let errors = match errors {
+        Some(e) => get_value(e),
+        None => "strict".to_string(),
+    };
+    let encoded = objcodecs::encode(vm, &value, &encoding, &errors)?;
+    Ok(vm.ctx.new_bytes(encoded))
 }
 
+// All slicing/indexing below operates on code points (Unicode scalar values),
+// not bytes, so that `start`/`stop`/`step` from PySliceableSequence::get_pos
+// and get_slice_items line up with what `len()` reports.
 impl PySliceableSequence for String {
     fn do_slice(&self, start: usize, stop: usize) -> Self {
-        self[start..stop].to_string()
+        self.chars().skip(start).take(stop - start).collect()
     }
     fn do_stepped_slice(&self, start: usize, stop: usize, step: usize) -> Self {
-        self[start..stop].chars().step_by(step).collect()
+        self.chars()
+            .skip(start)
+            .take(stop - start)
+            .step_by(step)
+            .collect()
     }
     fn len(&self) -> usize {
-        self.len()
+        self.chars().count()
     }
 }
 
@@ -400,7 +1395,10 @@ pub fn subscript(vm: &mut VirtualMachine, value: &str, b: PyObjectRef) -> PyResu
     if objtype::isinstance(&b, &vm.ctx.int_type()) {
         let pos = objint::get_value(&b).to_i32().unwrap();
         let idx = value.to_string().get_pos(pos);
-        Ok(vm.new_str(value[idx..idx + 1].to_string()))
+        match value.chars().nth(idx) {
+            Some(c) => Ok(vm.new_str(c.to_string())),
+            None => Err(vm.new_index_error("string index out of range".to_string())),
+        }
     } else {
         match &(*b.borrow()).kind {
             &PyObjectKind::Slice {